@@ -1,7 +1,9 @@
 use euclid::Size2D;
 use gdnative::{
-	methods, Basis, Color, Dictionary, File, GodotString, NativeClass, Node, Plane, Point2, Rect2,
-	Transform, Transform2D, Variant, VariantArray, Vector2, Vector3,
+	methods, Aabb, Basis, Color, ColorArray, Dictionary, File, Float32Array, GodotString,
+	Int32Array, NativeClass, Node, NodePath, Plane, Point2, Quat, Rect2, StringArray,
+	Transform, Transform2D, Variant, VariantArray, VariantType, Vector2, Vector2Array, Vector3,
+	Vector3Array,
 };
 
 use fancy_regex::Regex;
@@ -21,11 +23,18 @@ impl GodotToml {
 
 	/// Parses a toml file at a specified path and returns a dictonary populated with the values from it.
 	///
+	/// This is a thin wrapper around `parse_toml_string` that reads the file's contents through Godot's `File`
+	/// before handing them off to the shared parsing pipeline.
+	///
 	/// # Arguments
 	///
 	/// `path` - The path to the toml file to parse.
 	#[export]
 	fn parse_toml(&mut self, _owner: Node, path: GodotString) -> Dictionary {
+		// Keep a plain string copy of the path around for prefixing error messages below, since `file.open` takes
+		// ownership of the GodotString.
+		let path_as_string = Variant::to_string(&Variant::from_godot_string(&path));
+
 		// Open the file using Godot's File object.
 		let mut file = File::new();
 		match file.open(path, 3) {
@@ -38,138 +47,329 @@ impl GodotToml {
 			}
 		};
 
-		// Read the contents of the file as a string and then parse that string with the toml crate.
+		// Read the contents of the file and hand them off to parse_toml_string.
 		let file_to_godot_string = file.get_as_text();
-		let file_to_variant = Variant::from_godot_string(&file_to_godot_string);
-		let file_to_string = Variant::to_string(&file_to_variant);
-		let toml: Value =
-			toml::from_str(&file_to_string.to_owned()).expect("Unable to parse toml file.");
-		let toml_map = toml
-			.as_table()
-			.expect("Unable to get contents of toml file");
-		// Create the dictionary and then populate it using the contents of the toml file.
+
+		let mut dictionary = self.parse_toml_string(_owner, file_to_godot_string);
+		prefix_errors_with_path(&mut dictionary, &path_as_string);
+
+		return dictionary;
+	}
+
+	/// Parses toml contents already in memory and returns a dictonary populated with the values from it.
+	///
+	/// This is the entry point to use for toml that doesn't live on disk, e.g. config received over the network,
+	/// read from an encrypted resource, or typed into a text field. `parse_toml` is a thin wrapper around this that
+	/// reads a file first.
+	///
+	/// If any field fails to parse or convert, parsing continues with `null` substituted for that field and the
+	/// returned dictionary carries a `__errors__` key holding an array of human-readable messages describing what
+	/// went wrong, instead of crashing the running game.
+	///
+	/// # Arguments
+	///
+	/// `contents` - The toml contents to parse.
+	#[export]
+	fn parse_toml_string(&mut self, _owner: Node, contents: GodotString) -> Dictionary {
+		let contents_as_variant = Variant::from_godot_string(&contents);
+		let contents_as_string = Variant::to_string(&contents_as_variant);
+
+		let mut errors: Vec<String> = vec![];
 		let mut toml_dictionary = Dictionary::new();
-		populate_toml_dictionary(&toml, &mut toml_dictionary, toml_map);
+
+		match toml::from_str::<Value>(&contents_as_string) {
+			Ok(toml) => match toml.as_table() {
+				Some(toml_map) => populate_toml_dictionary(&toml, &mut toml_dictionary, toml_map, &mut errors),
+				None => errors.push("The root of the toml file is not a table.".to_string()),
+			},
+			Err(e) => errors.push(format!("Unable to parse toml file: {}", e)),
+		};
+
+		add_errors_to_dictionary(&mut toml_dictionary, &errors);
+
 		return toml_dictionary;
 	}
+
+	/// Serializes a dictionary back out to a toml file at the specified path.
+	///
+	/// Walks the dictionary recursively, mirroring `populate_toml_dictionary` in reverse, so that a file written by
+	/// `save_toml` re-parses identically through `parse_toml`. Known limitation: a plain `GodotString` whose text
+	/// happens to match a Godot-type literal exactly (e.g. `"Vector2( 1, 2 )"`) is indistinguishable on disk from
+	/// an actual `Vector2`, so it comes back as the typed value instead of the original string. A homogeneous array
+	/// of integers, floats, strings, Vector2s, Vector3s or Colors also comes back as the matching pool array rather
+	/// than a generic array, with integer and float elements narrowed to 32 bits.
+	///
+	/// # Arguments
+	///
+	/// `path` - The path to write the toml file to.
+	/// `data` - The dictionary to serialize.
+	#[export]
+	fn save_toml(&mut self, _owner: Node, path: GodotString, data: Dictionary) {
+		let toml_table = dictionary_to_toml_table(&data);
+		let toml_string = match toml::to_string(&toml_table) {
+			Ok(s) => s,
+			Err(e) => {
+				godot_print!(
+					"Unable to serialize dictionary to toml with an error of {:?}. Please make sure every array in the dictionary only contains values of a single type",
+					e
+				);
+				return;
+			}
+		};
+
+		// Open the file using Godot's File object in write mode.
+		let mut file = File::new();
+		match file.open(path, 2) {
+			Ok(_v) => (),
+			Err(e) => {
+				godot_print!(
+					"Unable to open file with an error of {:?}. Please make sure the path to file is correct",
+					e
+				);
+				return;
+			}
+		};
+
+		file.store_string(GodotString::from_str(&toml_string));
+	}
+}
+
+/// Sets the `__errors__` key on a dictionary to the provided messages, if there are any.
+///
+/// # Arguments
+///
+/// `dictionary` - The dictionary to add the errors to.
+/// `errors` - The error messages collected while parsing.
+fn add_errors_to_dictionary(dictionary: &mut Dictionary, errors: &Vec<String>) {
+	if errors.is_empty() {
+		return;
+	}
+
+	let mut error_arr = VariantArray::new();
+	for error in errors {
+		error_arr.push(&Variant::from_str(error));
+	}
+
+	dictionary.set(
+		&Variant::from_str("__errors__"),
+		&Variant::from_array(&error_arr),
+	);
+}
+
+/// Prefixes every message in a dictionary's `__errors__` array with the path of the file that produced them, so a
+/// caller juggling multiple config files can tell which one an error came from. Does nothing if the dictionary has
+/// no `__errors__` key.
+///
+/// # Arguments
+///
+/// `dictionary` - The dictionary to rewrite the `__errors__` array of.
+/// `path` - The path to prefix every error message with.
+fn prefix_errors_with_path(dictionary: &mut Dictionary, path: &str) {
+	let errors_key = Variant::from_str("__errors__");
+	let errors = dictionary.get(&errors_key);
+	if errors.get_type() != VariantType::VariantArray {
+		return;
+	}
+
+	let mut prefixed_errors = VariantArray::new();
+	for error in errors.to_array().iter() {
+		let message = Variant::to_string(&error);
+		prefixed_errors.push(&Variant::from_str(&format!("{}: {}", path, message)));
+	}
+
+	dictionary.set(&errors_key, &Variant::from_array(&prefixed_errors));
 }
 
 /// Populates a dictonary with the parsed values of the toml table provided.
 ///
 /// If a value contains a string, it is further parsed by convert_godot_types which checks to see if the string is a Godot type and then performs
-/// the necessary conversions on it and adds it to the dictionary.
+/// the necessary conversions on it and adds it to the dictionary. Known limitation: this means a plain string that
+/// exactly matches a Godot-type literal (e.g. `"Vector2( 1, 2 )"`) is indistinguishable from an actual encoded
+/// value of that type and gets converted, see `variant_to_toml_value`.
+///
+/// Any field that can't be converted has its failure recorded in `errors` and is set to `null` in the dictionary
+/// instead of panicking, so a single malformed field doesn't take down the rest of the file.
 ///
 /// # Arguments
 ///
 /// `toml` - The parsed toml.
 /// `dictionary` - The dictionary to populate.
 /// `table` - The Table from the parsed toml.
+/// `errors` - Accumulates human-readable messages for any field that fails to convert.
 fn populate_toml_dictionary(
 	toml: &Value,
 	dictionary: &mut Dictionary,
 	table: &toml::map::Map<std::string::String, Value>,
+	errors: &mut Vec<String>,
 ) {
 	for (key, value) in table {
 		let field_type = value.type_str();
 		match field_type {
-			"table" => {
-				let sub_dic = &mut Dictionary::new();
-				let new_table = table[key]
-					.as_table()
-					.expect("Unable to cast value to table");
-				populate_toml_dictionary(toml, sub_dic, new_table);
-				dictionary.set(&Variant::from_str(key), &Variant::from_dictionary(sub_dic));
-			}
-			"array" => {
-				let mut dictionary_arr = VariantArray::new();
-				let toml_arr = table[key]
-					.as_array()
-					.expect("Unable to cast value to array");
-				for i in toml_arr {
+			"table" => match table[key].as_table() {
+				Some(new_table) => {
 					let sub_dic = &mut Dictionary::new();
-					populate_toml_dictionary(toml, sub_dic, i.as_table().unwrap());
-					dictionary_arr.push(&Variant::from_dictionary(sub_dic));
+					populate_toml_dictionary(toml, sub_dic, new_table, errors);
+					dictionary.set(&Variant::from_str(key), &Variant::from_dictionary(sub_dic));
 				}
-				dictionary.set(
-					&Variant::from_str(key),
-					&Variant::from_array(&dictionary_arr),
-				)
-			}
-			"integer" => dictionary.set(
-				&Variant::from_str(key),
-				&Variant::from_i64(value.as_integer().expect("Unable to cast value to integer")),
-			),
-			"string" => {
-				let value_as_str = value.as_str().expect("Unable to cast value to string");
-				// A simple check to that we can avoid the cost of regex if we don't need to is to check if the string contains a parenthesis.
-				if value_as_str.contains("(") {
-					encode_godot_types(dictionary, key, value_as_str);
-				} else {
-					dictionary.set(&Variant::from_str(key), &Variant::from_str(value_as_str))
+				None => {
+					errors.push(format!("'{}' could not be cast to a table.", key));
+					dictionary.set(&Variant::from_str(key), &Variant::new());
 				}
-			}
-			"float" => dictionary.set(
-				&Variant::from_str(key),
-				&Variant::from_f64(value.as_float().expect("Unable to cast value to float")),
-			),
-			"boolean" => dictionary.set(
-				&Variant::from_str(key),
-				&Variant::from_bool(value.as_bool().expect("Unable to cast value to bool")),
-			),
-			"datetime" => dictionary.set(
-				&Variant::from_str(key),
-				&Variant::from_str(
-					value
-						.as_datetime()
-						.expect("Unable to cast value to float")
-						.to_string(),
-				),
-			),
+			},
+			"array" => match table[key].as_array() {
+				Some(toml_arr) => {
+					dictionary.set(&Variant::from_str(key), &encode_array(toml, toml_arr, errors))
+				}
+				None => {
+					errors.push(format!("'{}' could not be cast to an array.", key));
+					dictionary.set(&Variant::from_str(key), &Variant::new());
+				}
+			},
+			"integer" => match value.as_integer() {
+				Some(v) => dictionary.set(&Variant::from_str(key), &Variant::from_i64(v)),
+				None => {
+					errors.push(format!("'{}' could not be cast to an integer.", key));
+					dictionary.set(&Variant::from_str(key), &Variant::new());
+				}
+			},
+			"string" => match value.as_str() {
+				Some(value_as_str) => {
+					// A simple check to that we can avoid the cost of regex if we don't need to is to check if the string contains a parenthesis.
+					if value_as_str.contains("(") {
+						encode_godot_types(dictionary, key, value_as_str, errors);
+					} else {
+						dictionary.set(&Variant::from_str(key), &Variant::from_str(value_as_str))
+					}
+				}
+				None => {
+					errors.push(format!("'{}' could not be cast to a string.", key));
+					dictionary.set(&Variant::from_str(key), &Variant::new());
+				}
+			},
+			"float" => match value.as_float() {
+				Some(v) => dictionary.set(&Variant::from_str(key), &Variant::from_f64(v)),
+				None => {
+					errors.push(format!("'{}' could not be cast to a float.", key));
+					dictionary.set(&Variant::from_str(key), &Variant::new());
+				}
+			},
+			"boolean" => match value.as_bool() {
+				Some(v) => dictionary.set(&Variant::from_str(key), &Variant::from_bool(v)),
+				None => {
+					errors.push(format!("'{}' could not be cast to a boolean.", key));
+					dictionary.set(&Variant::from_str(key), &Variant::new());
+				}
+			},
+			"datetime" => match value.as_datetime() {
+				Some(v) => dictionary.set(&Variant::from_str(key), &Variant::from_str(&v.to_string())),
+				None => {
+					errors.push(format!("'{}' could not be cast to a datetime.", key));
+					dictionary.set(&Variant::from_str(key), &Variant::new());
+				}
+			},
 			_ => (),
 		}
 	}
 }
 
-/// Checks to see if a toml string is a Godot type and if so use `set_godot_type_to_dictionary`
+/// Checks to see if a toml string is a Godot type and if so use `set_godot_type_to_variant`
 ///
 /// # Arguments
 ///
 /// `dictionary` - A reference to the dictionary so that the Godot types can be added to it.
 /// `key` - The key of the current string item being checked.
 /// `value` - The value of the current string item being checked.
-fn encode_godot_types(dictionary: &mut Dictionary, key: &str, value: &str) {
+/// `errors` - Accumulates human-readable messages for any Godot type that fails to convert.
+fn encode_godot_types(dictionary: &mut Dictionary, key: &str, value: &str, errors: &mut Vec<String>) {
+	let errors_before = errors.len();
+	match godot_type_string_to_variant(value, errors) {
+		Some(variant) => dictionary.set(&Variant::from_str(&key), &variant),
+		// The string looked like a Godot type but failed to convert, so the field is set to null.
+		None if errors.len() > errors_before => dictionary.set(&Variant::from_str(&key), &Variant::new()),
+		None => dictionary.set(&Variant::from_str(&key), &Variant::from_str(&value)),
+	}
+}
+
+/// The Godot types that can appear inside a parenthesized string, e.g. `Vector2( 1, 2 )`.
+const GODOT_TYPES: [&str; 10] = [
+	"Vector2",
+	"Vector3",
+	"Color",
+	"Rect2",
+	"Plane",
+	"Transform2D",
+	"Basis",
+	"Transform",
+	"Quat",
+	"AABB",
+];
+
+/// Checks to see if a string is a Godot type and if so converts it into the Variant it represents.
+///
+/// Returns `None` if the string doesn't match any of the known Godot types, in which case the caller should fall
+/// back to treating it as a plain string. A string that looks like a Godot type but is missing or has malformed
+/// components (e.g. a truncated `Vector2( 1 )`) records a message in `errors` and also returns `None`.
+///
+/// # Arguments
+///
+/// `value` - The string to check and convert.
+/// `errors` - Accumulates human-readable messages for any Godot type that fails to convert.
+fn godot_type_string_to_variant(value: &str, errors: &mut Vec<String>) -> Option<Variant> {
+	// A NodePath is just a string literal wrapped in `NodePath( "..." )`, so it can't go through the generic
+	// tokenizer below since its contents aren't restricted to word characters.
+	if let Some(node_path) = encode_nodepath(value) {
+		return Some(Variant::from_node_path(&node_path));
+	}
+
 	// Create a pattern to check for Godot types (Vector2, Rect2, etc.) and check to see if there are any matches in the string.
 	// let type_re = Regex::new(r"((?:\/)?(\w+))").expect("Unable to create regex for type");
-	let type_re =
-		Regex::new(r"((?:\/)?([a-zA-Z0-9\.]+))").expect("Unable to create regex for type");
+	let type_re = match Regex::new(r"((?:\/)?([a-zA-Z0-9\.\-]+))") {
+		Ok(re) => re,
+		Err(_) => {
+			errors.push("Unable to create the regex used to detect Godot types.".to_string());
+			return None;
+		}
+	};
 	let mut type_idx = 0;
 	let mut type_results: Vec<&str> = vec![];
-	while let Some(t) = type_re
-		.captures_from_pos(value, type_idx)
-		.expect("Unable to get captures")
-	{
-		type_results.push(t.get(1).expect("Unable to get capture group").as_str());
-		type_idx = t.get(0).expect("Unable to get capture group").end();
+	loop {
+		match type_re.captures_from_pos(value, type_idx) {
+			Ok(Some(t)) => match (t.get(1), t.get(0)) {
+				(Some(group), Some(whole)) => {
+					type_results.push(group.as_str());
+					type_idx = whole.end();
+				}
+				_ => break,
+			},
+			Ok(None) => break,
+			Err(_) => {
+				errors.push(format!("Unable to parse '{}' while looking for a Godot type.", value));
+				return None;
+			}
+		}
 	}
 
-	let godot_types: [&str; 8] = [
-		"Vector2",
-		"Vector3",
-		"Color",
-		"Rect2",
-		"Plane",
-		"Transform2D",
-		"Basis",
-		"Transform",
-	];
-
-	if !godot_types.contains(&type_results[0]) {
-		dictionary.set(&Variant::from_str(&key), &Variant::from_str(&value));
-		return;
+	match type_results.first() {
+		Some(first) if GODOT_TYPES.contains(first) => (),
+		_ => return None,
 	}
 
 	// If there is a pattern then we need to decode the regex results into the Godot type.
-	set_godot_type_to_dictionary(type_results, key, dictionary, &mut None, &mut None);
+	set_godot_type_to_variant(type_results, &mut None, &mut None, errors)
+}
+
+/// Returns a NodePath parsed from its `NodePath( "path/to/node" )` string form, or `None` if the string isn't a
+/// NodePath.
+///
+/// # Arguments
+///
+/// `value` - The string to check and parse.
+fn encode_nodepath(value: &str) -> Option<NodePath> {
+	let node_path_re = Regex::new(r#"^NodePath\(\s*"(.*)"\s*\)$"#).ok()?;
+	let captures = node_path_re.captures(value.trim()).ok()??;
+	let path = captures.get(1)?.as_str();
+
+	Some(NodePath::from_str(path))
 }
 
 /// Returns a Vector2 at the specified (x, y) location.
@@ -178,11 +378,17 @@ fn encode_godot_types(dictionary: &mut Dictionary, key: &str, value: &str) {
 ///
 /// `x` - The x position of the Vector2.
 /// `y` - The y position of the Vector2.
-fn encode_vector2(x: &str, y: &str) -> Vector2 {
-	let vec_x: f32 = x.trim().parse().expect("Unable to cast to f32");
-	let vec_y: f32 = y.trim().parse().expect("Unable to cast to f32");
+fn encode_vector2(x: &str, y: &str) -> Result<Vector2, String> {
+	let vec_x: f32 = x
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Vector2.", x))?;
+	let vec_y: f32 = y
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Vector2.", y))?;
 
-	return Vector2::new(vec_x, vec_y);
+	Ok(Vector2::new(vec_x, vec_y))
 }
 
 /// Returns a Vector3 with the specified x, y, and z values.
@@ -192,12 +398,21 @@ fn encode_vector2(x: &str, y: &str) -> Vector2 {
 /// `x` - The x value of the Vector3.
 /// `y` - The y value of the Vector3.
 /// `z` - The z value of the Vector3.
-fn encode_vector3(x: &str, y: &str, z: &str) -> Vector3 {
-	let vec_x: f32 = x.trim().parse().expect("Unable to cast to f32");
-	let vec_y: f32 = y.trim().parse().expect("Unable to cast to f32");
-	let vec_z: f32 = z.trim().parse().expect("Unable to cast to f32");
+fn encode_vector3(x: &str, y: &str, z: &str) -> Result<Vector3, String> {
+	let vec_x: f32 = x
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Vector3.", x))?;
+	let vec_y: f32 = y
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Vector3.", y))?;
+	let vec_z: f32 = z
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Vector3.", z))?;
 
-	return Vector3::new(vec_x, vec_y, vec_z);
+	Ok(Vector3::new(vec_x, vec_y, vec_z))
 }
 
 /// Returns a Rect2 at the specified point and with the specified size.
@@ -259,13 +474,16 @@ fn encode_transform(
 ///
 /// `normal_vec` - The Plane's normal vector.
 /// `d` - The d value of the Plane.
-fn encode_plane(normal_vec: Vector3, d: &str) -> Plane {
-	let d_parsed: f32 = d.trim().parse().expect("Unable to cast to f32");
+fn encode_plane(normal_vec: Vector3, d: &str) -> Result<Plane, String> {
+	let d_parsed: f32 = d
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Plane's d component.", d))?;
 
-	return Plane {
+	Ok(Plane {
 		normal: normal_vec,
 		d: d_parsed,
-	};
+	})
 }
 
 /// Returns a 3x3 matrix used consisting of Vector3 values for x, y, and z.
@@ -289,166 +507,995 @@ fn encode_basis(x: Vector3, y: Vector3, z: Vector3) -> Basis {
 /// `g` - The green value of the color.
 /// `b` - The blue value of the color.
 /// `a` - The optional alpha value of the color.
-fn encode_color(r: &str, g: &str, b: &str, a: Option<&str>) -> Color {
-	let r_parsed: f32 = r.trim().parse().expect("Unable to cast to float");
-	let g_parsed: f32 = g.trim().parse().expect("Unable to cast to float");
-	let b_parsed: f32 = b.trim().parse().expect("Unable to cast to float");
+fn encode_color(r: &str, g: &str, b: &str, a: Option<&str>) -> Result<Color, String> {
+	let r_parsed: f32 = r
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Color.", r))?;
+	let g_parsed: f32 = g
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Color.", g))?;
+	let b_parsed: f32 = b
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Color.", b))?;
 
 	match a {
 		Some(alpha) => {
-			let a_parsed: f32 = alpha.trim().parse().expect("Unable to cast to float");
-			return Color::rgba(r_parsed, g_parsed, b_parsed, a_parsed);
+			let a_parsed: f32 = alpha
+				.trim()
+				.parse()
+				.map_err(|_| format!("'{}' is not a valid number for a Color.", alpha))?;
+			Ok(Color::rgba(r_parsed, g_parsed, b_parsed, a_parsed))
 		}
-		None => return Color::rgb(r_parsed, g_parsed, b_parsed),
+		None => Ok(Color::rgb(r_parsed, g_parsed, b_parsed)),
+	}
+}
+
+/// Returns a Quat with the specified x, y, z, and w values.
+///
+/// # Arguments
+///
+/// `x` - The x value of the Quat.
+/// `y` - The y value of the Quat.
+/// `z` - The z value of the Quat.
+/// `w` - The w value of the Quat.
+fn encode_quat(x: &str, y: &str, z: &str, w: &str) -> Result<Quat, String> {
+	let quat_x: f32 = x
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Quat.", x))?;
+	let quat_y: f32 = y
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Quat.", y))?;
+	let quat_z: f32 = z
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Quat.", z))?;
+	let quat_w: f32 = w
+		.trim()
+		.parse()
+		.map_err(|_| format!("'{}' is not a valid number for a Quat.", w))?;
+
+	Ok(Quat::new(quat_x, quat_y, quat_z, quat_w))
+}
+
+/// Returns an AABB with the specified position and size Vector3s.
+///
+/// # Arguments
+///
+/// `position_vec` - The Vector3 that defines the AABB's position.
+/// `size_vec` - The Vector3 that defines the AABB's size.
+fn encode_aabb(position_vec: Vector3, size_vec: Vector3) -> Aabb {
+	return Aabb {
+		position: position_vec,
+		size: size_vec,
 	};
 }
 
-/// Takes the results of the regex provided by `convert_godot_types` and determines what Godot type needs to be created
-/// and added to the dictionary.
+/// Takes the results of the regex provided by `godot_type_string_to_variant` and determines what Godot type needs
+/// to be created, returning it as a Variant.
+///
+/// Any type that is missing components (e.g. a truncated `Vector2( 1 )`) or has a component that fails to parse
+/// records a message in `errors` and returns `None` instead of panicking.
 ///
 /// # Arguments
 ///
-/// `regex_results` - The vector of results from `convert_godot_types`.
-/// `key` - The key of the current item.
-/// `dictionary` - A reference to the dictionary so that Godot types can be added to it.
+/// `regex_results` - The vector of results from `godot_type_string_to_variant`.
 /// `vec2_pool` - An optional pool of Vector2s that is used when this function is called recursively for complex types made up of Vector2s.
 /// `vec3_pool` - An optional pool of Vector3s that is used when this function is called recursively for complex types made up of Vector3s.
-fn set_godot_type_to_dictionary(
+/// `errors` - Accumulates human-readable messages for any Godot type that fails to convert.
+fn set_godot_type_to_variant(
 	regex_results: Vec<&str>,
-	key: &str,
-	dictionary: &mut Dictionary,
 	vec2_pool: &mut Option<&mut Vec<Vector2>>,
 	vec3_pool: &mut Option<&mut Vec<Vector3>>,
-) {
+	errors: &mut Vec<String>,
+) -> Option<Variant> {
 	for (i, item) in regex_results.iter().enumerate() {
 		match item {
 			&"Vector2" => {
-				let vector2 = encode_vector2(regex_results[i + 1], regex_results[i + 2]);
-				match vec2_pool {
-					Some(x) => x.push(vector2),
-					None => {
-						dictionary.set(&Variant::from_str(&key), &Variant::from_vector2(&vector2));
-						break;
+				let (x, y) = match (regex_results.get(i + 1), regex_results.get(i + 2)) {
+					(Some(x), Some(y)) => (*x, *y),
+					_ => {
+						errors.push("Vector2 is missing its x or y component.".to_string());
+						return None;
+					}
+				};
+
+				match encode_vector2(x, y) {
+					Ok(vector2) => match vec2_pool {
+						Some(pool) => pool.push(vector2),
+						None => return Some(Variant::from_vector2(&vector2)),
+					},
+					Err(e) => {
+						errors.push(e);
+						return None;
 					}
 				}
 			}
 			&"Vector3" => {
-				let vector3 = encode_vector3(
-					regex_results[i + 1],
-					regex_results[i + 2],
-					regex_results[i + 3],
-				);
-				match vec3_pool {
-					Some(x) => x.push(vector3),
-					None => {
-						dictionary.set(&Variant::from_str(&key), &Variant::from_vector3(&vector3));
-						break;
+				let (x, y, z) = match (
+					regex_results.get(i + 1),
+					regex_results.get(i + 2),
+					regex_results.get(i + 3),
+				) {
+					(Some(x), Some(y), Some(z)) => (*x, *y, *z),
+					_ => {
+						errors.push("Vector3 is missing one of its x, y, or z components.".to_string());
+						return None;
+					}
+				};
+
+				match encode_vector3(x, y, z) {
+					Ok(vector3) => match vec3_pool {
+						Some(pool) => pool.push(vector3),
+						None => return Some(Variant::from_vector3(&vector3)),
+					},
+					Err(e) => {
+						errors.push(e);
+						return None;
 					}
 				};
 			}
 			&"Color" => {
-				let mut alpha: std::option::Option<&str> = None;
-				if regex_results.len() == 5 {
-					alpha = Some(regex_results[i + 4]);
-				}
-				let color = encode_color(
-					regex_results[i + 1],
-					regex_results[i + 2],
-					regex_results[i + 3],
-					alpha,
-				);
-				dictionary.set(&Variant::from_str(&key), &Variant::from_color(&color));
-				break;
+				let (r, g, b) = match (
+					regex_results.get(i + 1),
+					regex_results.get(i + 2),
+					regex_results.get(i + 3),
+				) {
+					(Some(r), Some(g), Some(b)) => (*r, *g, *b),
+					_ => {
+						errors.push("Color is missing one of its r, g, or b components.".to_string());
+						return None;
+					}
+				};
+				let alpha = regex_results.get(i + 4).copied();
+
+				match encode_color(r, g, b, alpha) {
+					Ok(color) => return Some(Variant::from_color(&color)),
+					Err(e) => {
+						errors.push(e);
+						return None;
+					}
+				}
 			}
 			&"Rect2" => {
 				// Since a Rect2 is a complex type that consists of Vector2's, we need to run the this function recursively to get
 				// the Vector2 position and Vector2 size values.
 				let new_regex_results = regex_results[i + 1..regex_results.len()].to_vec();
 				let vec2_pool: &mut Vec<Vector2> = &mut vec![];
-				set_godot_type_to_dictionary(
-					new_regex_results,
-					key,
-					dictionary,
-					&mut Some(vec2_pool),
-					&mut None,
-				);
+				set_godot_type_to_variant(new_regex_results, &mut Some(vec2_pool), &mut None, errors);
+
+				if vec2_pool.len() < 2 {
+					errors.push("Rect2 is missing its position or size Vector2.".to_string());
+					return None;
+				}
 
 				let rect2 = encode_rect2(vec2_pool[0], vec2_pool[1]);
-				dictionary.set(&Variant::from_str(&key), &Variant::from_rect2(&rect2));
-				break;
+				return Some(Variant::from_rect2(&rect2));
 			}
 			&"Plane" => {
 				// Plane is a complex type made up of a Vector3 and a float so we need to use recursion to get the Vector value.
 				let new_regex_results = regex_results[i + 1..regex_results.len()].to_vec();
 				let vec3_pool: &mut Vec<Vector3> = &mut vec![];
-				set_godot_type_to_dictionary(
-					new_regex_results,
-					key,
-					dictionary,
-					&mut None,
-					&mut Some(vec3_pool),
-				);
+				set_godot_type_to_variant(new_regex_results, &mut None, &mut Some(vec3_pool), errors);
+
+				if vec3_pool.is_empty() {
+					errors.push("Plane is missing its normal Vector3.".to_string());
+					return None;
+				}
 
-				let plane = encode_plane(vec3_pool[0], regex_results[regex_results.len() - 1]);
-				dictionary.set(&Variant::from_str(&key), &Variant::from_plane(&plane));
-				break;
+				let d = match regex_results.last() {
+					Some(d) => *d,
+					None => {
+						errors.push("Plane is missing its d component.".to_string());
+						return None;
+					}
+				};
+
+				match encode_plane(vec3_pool[0], d) {
+					Ok(plane) => return Some(Variant::from_plane(&plane)),
+					Err(e) => {
+						errors.push(e);
+						return None;
+					}
+				}
 			}
 			&"Transform2D" => {
 				// Transform2D is a complex type made up of three Vector2s so we need to use recursion to get the Vector2 values.
 				let new_regex_results = regex_results[i + 1..regex_results.len()].to_vec();
 				let vec2_pool: &mut Vec<Vector2> = &mut vec![];
-				set_godot_type_to_dictionary(
-					new_regex_results,
-					key,
-					dictionary,
-					&mut Some(vec2_pool),
-					&mut None,
-				);
+				set_godot_type_to_variant(new_regex_results, &mut Some(vec2_pool), &mut None, errors);
+
+				if vec2_pool.len() < 3 {
+					errors.push("Transform2D is missing one of its x, y, or origin Vector2s.".to_string());
+					return None;
+				}
 
 				let transform2d = encode_transform2d(vec2_pool[0], vec2_pool[1], vec2_pool[2]);
-				dictionary.set(
-					&Variant::from_str(&key),
-					&Variant::from_transform2d(&transform2d),
-				);
-				break;
+				return Some(Variant::from_transform2d(&transform2d));
 			}
 			&"Basis" => {
 				// Basis is a complex type made up to three Vector3s so we need to use recursion to get the Vector3 values.
 				let new_regex_results = regex_results[i + 1..regex_results.len()].to_vec();
 				let vec3_pool: &mut Vec<Vector3> = &mut vec![];
-				set_godot_type_to_dictionary(
-					new_regex_results,
-					key,
-					dictionary,
-					&mut None,
-					&mut Some(vec3_pool),
-				);
+				set_godot_type_to_variant(new_regex_results, &mut None, &mut Some(vec3_pool), errors);
+
+				if vec3_pool.len() < 3 {
+					errors.push("Basis is missing one of its x, y, or z Vector3s.".to_string());
+					return None;
+				}
 
 				let basis = encode_basis(vec3_pool[0], vec3_pool[1], vec3_pool[2]);
-				dictionary.set(&Variant::from_str(&key), &Variant::from_basis(&basis));
-				break;
+				return Some(Variant::from_basis(&basis));
 			}
 			&"Transform" => {
 				// Transform is a complex type made up of four Vector3s so we need to use recursion to get the Vector3 values.
 				let new_regex_results = regex_results[i + 1..regex_results.len()].to_vec();
 				let vec3_pool: &mut Vec<Vector3> = &mut vec![];
-				set_godot_type_to_dictionary(
-					new_regex_results,
-					key,
-					dictionary,
-					&mut None,
-					&mut Some(vec3_pool),
-				);
+				set_godot_type_to_variant(new_regex_results, &mut None, &mut Some(vec3_pool), errors);
+
+				if vec3_pool.len() < 4 {
+					errors.push("Transform is missing one of its x, y, z, or origin Vector3s.".to_string());
+					return None;
+				}
 
 				let transform =
 					encode_transform(vec3_pool[0], vec3_pool[1], vec3_pool[2], vec3_pool[3]);
-				dictionary.set(
-					&Variant::from_str(&key),
-					&Variant::from_transform(&transform),
-				);
-				break;
+				return Some(Variant::from_transform(&transform));
+			}
+			&"Quat" => {
+				let (x, y, z, w) = match (
+					regex_results.get(i + 1),
+					regex_results.get(i + 2),
+					regex_results.get(i + 3),
+					regex_results.get(i + 4),
+				) {
+					(Some(x), Some(y), Some(z), Some(w)) => (*x, *y, *z, *w),
+					_ => {
+						errors.push("Quat is missing one of its x, y, z, or w components.".to_string());
+						return None;
+					}
+				};
+
+				match encode_quat(x, y, z, w) {
+					Ok(quat) => return Some(Variant::from_quat(&quat)),
+					Err(e) => {
+						errors.push(e);
+						return None;
+					}
+				}
+			}
+			&"AABB" => {
+				// AABB is a complex type made up of two Vector3s (position and size) so we reuse the same
+				// Vector3-pool recursion that Transform and Basis use.
+				let new_regex_results = regex_results[i + 1..regex_results.len()].to_vec();
+				let vec3_pool: &mut Vec<Vector3> = &mut vec![];
+				set_godot_type_to_variant(new_regex_results, &mut None, &mut Some(vec3_pool), errors);
+
+				if vec3_pool.len() < 2 {
+					errors.push("AABB is missing its position or size Vector3.".to_string());
+					return None;
+				}
+
+				let aabb = encode_aabb(vec3_pool[0], vec3_pool[1]);
+				return Some(Variant::from_aabb(&aabb));
 			}
 			_ => (),
 		}
 	}
+
+	return None;
+}
+
+/// Builds the Godot variant for a toml array.
+///
+/// Tables recurse into sub-dictionaries, scalars and Godot-typed strings are converted directly, and a
+/// homogeneous primitive/Godot-typed array is packed into the matching Godot pool array (`PoolIntArray`,
+/// `PoolRealArray`, `PoolStringArray`, `PoolVector2Array`, `PoolVector3Array`, `PoolColorArray`) instead of a
+/// generic `VariantArray`. Elements that fail to convert are recorded in `errors` and set to `null` instead of
+/// panicking.
+///
+/// # Arguments
+///
+/// `toml` - The parsed toml, needed so table elements can recurse through `populate_toml_dictionary`.
+/// `toml_arr` - The array of toml values to convert.
+/// `errors` - Accumulates human-readable messages for any element that fails to convert.
+fn encode_array(toml: &Value, toml_arr: &Vec<Value>, errors: &mut Vec<String>) -> Variant {
+	if let Some(pool_variant) = encode_pool_array(toml_arr, errors) {
+		return pool_variant;
+	}
+
+	let mut dictionary_arr = VariantArray::new();
+	for i in toml_arr {
+		match i.type_str() {
+			"table" => match i.as_table() {
+				Some(t) => {
+					let sub_dic = &mut Dictionary::new();
+					populate_toml_dictionary(toml, sub_dic, t, errors);
+					dictionary_arr.push(&Variant::from_dictionary(sub_dic));
+				}
+				None => {
+					errors.push("An array element could not be cast to a table.".to_string());
+					dictionary_arr.push(&Variant::new());
+				}
+			},
+			"array" => match i.as_array() {
+				Some(nested_arr) => dictionary_arr.push(&encode_array(toml, nested_arr, errors)),
+				None => {
+					errors.push("An array element could not be cast to an array.".to_string());
+					dictionary_arr.push(&Variant::new());
+				}
+			},
+			"integer" => match i.as_integer() {
+				Some(v) => dictionary_arr.push(&Variant::from_i64(v)),
+				None => {
+					errors.push("An array element could not be cast to an integer.".to_string());
+					dictionary_arr.push(&Variant::new());
+				}
+			},
+			"float" => match i.as_float() {
+				Some(v) => dictionary_arr.push(&Variant::from_f64(v)),
+				None => {
+					errors.push("An array element could not be cast to a float.".to_string());
+					dictionary_arr.push(&Variant::new());
+				}
+			},
+			"boolean" => match i.as_bool() {
+				Some(v) => dictionary_arr.push(&Variant::from_bool(v)),
+				None => {
+					errors.push("An array element could not be cast to a boolean.".to_string());
+					dictionary_arr.push(&Variant::new());
+				}
+			},
+			"string" => match i.as_str() {
+				Some(value_as_str) => {
+					if value_as_str.contains("(") {
+						let errors_before = errors.len();
+						match godot_type_string_to_variant(value_as_str, errors) {
+							Some(variant) => dictionary_arr.push(&variant),
+							None if errors.len() > errors_before => dictionary_arr.push(&Variant::new()),
+							None => dictionary_arr.push(&Variant::from_str(value_as_str)),
+						}
+					} else {
+						dictionary_arr.push(&Variant::from_str(value_as_str));
+					}
+				}
+				None => {
+					errors.push("An array element could not be cast to a string.".to_string());
+					dictionary_arr.push(&Variant::new());
+				}
+			},
+			"datetime" => match i.as_datetime() {
+				Some(v) => dictionary_arr.push(&Variant::from_str(&v.to_string())),
+				None => {
+					errors.push("An array element could not be cast to a datetime.".to_string());
+					dictionary_arr.push(&Variant::new());
+				}
+			},
+			_ => (),
+		}
+	}
+
+	return Variant::from_array(&dictionary_arr);
+}
+
+/// Attempts to pack a homogeneous toml array of primitives or Godot types into the matching Godot pool array.
+///
+/// Returns `None` if the array is empty, mixed, contains tables, or holds a type that has no pool array
+/// equivalent, in which case the caller falls back to building a generic `VariantArray`.
+///
+/// # Arguments
+///
+/// `toml_arr` - The array of toml values to try to pack.
+/// `errors` - Accumulates human-readable messages for any element that fails to convert.
+fn encode_pool_array(toml_arr: &Vec<Value>, errors: &mut Vec<String>) -> Option<Variant> {
+	if toml_arr.is_empty() {
+		return None;
+	}
+
+	let first_type = toml_arr[0].type_str();
+	if !toml_arr.iter().all(|i| i.type_str() == first_type) {
+		return None;
+	}
+
+	match first_type {
+		"integer" => {
+			let mut pool = Int32Array::new();
+			for i in toml_arr {
+				match i.as_integer() {
+					Some(v) => pool.push(v as i32),
+					None => errors.push("An array element could not be cast to an integer.".to_string()),
+				}
+			}
+			Some(Variant::from_int32_array(&pool))
+		}
+		"float" => {
+			let mut pool = Float32Array::new();
+			for i in toml_arr {
+				match i.as_float() {
+					Some(v) => pool.push(v as f32),
+					None => errors.push("An array element could not be cast to a float.".to_string()),
+				}
+			}
+			Some(Variant::from_float32_array(&pool))
+		}
+		"string" => {
+			// Strings could be plain strings or encoded Godot types (e.g. Vector2), so decode them first and then
+			// see if the decoded variants are all of the same Godot type. This decode is only a trial: errors are
+			// collected into a scratch vec, and if there are any the array is left to `encode_array`'s generic
+			// loop, which decodes every element again, records each error once and sets the failed elements to
+			// `null` (a pool array can't hold a `null`).
+			let mut trial_errors: Vec<String> = vec![];
+			let decoded: Vec<Variant> = toml_arr
+				.iter()
+				.map(|i| {
+					let value_as_str = match i.as_str() {
+						Some(v) => v,
+						None => {
+							trial_errors.push("An array element could not be cast to a string.".to_string());
+							return Variant::new();
+						}
+					};
+
+					if value_as_str.contains("(") {
+						godot_type_string_to_variant(value_as_str, &mut trial_errors)
+					} else {
+						None
+					}
+					.unwrap_or(Variant::from_str(value_as_str))
+				})
+				.collect();
+
+			if !trial_errors.is_empty() {
+				return None;
+			}
+
+			encode_variant_pool_array(&decoded)
+		}
+		_ => None,
+	}
+}
+
+/// Packs a homogeneous array of decoded variants into the matching Godot pool array.
+///
+/// # Arguments
+///
+/// `items` - The decoded variants to try to pack.
+fn encode_variant_pool_array(items: &Vec<Variant>) -> Option<Variant> {
+	let first_type = items[0].get_type();
+	if !items.iter().all(|i| i.get_type() == first_type) {
+		return None;
+	}
+
+	match first_type {
+		VariantType::GodotString => {
+			let mut pool = StringArray::new();
+			for i in items {
+				pool.push(&i.to_godot_string());
+			}
+			Some(Variant::from_string_array(&pool))
+		}
+		VariantType::Vector2 => {
+			let mut pool = Vector2Array::new();
+			for i in items {
+				pool.push(&i.to_vector2());
+			}
+			Some(Variant::from_vector2_array(&pool))
+		}
+		VariantType::Vector3 => {
+			let mut pool = Vector3Array::new();
+			for i in items {
+				pool.push(&i.to_vector3());
+			}
+			Some(Variant::from_vector3_array(&pool))
+		}
+		VariantType::Color => {
+			let mut pool = ColorArray::new();
+			for i in items {
+				pool.push(&i.to_color());
+			}
+			Some(Variant::from_color_array(&pool))
+		}
+		_ => None,
+	}
+}
+
+/// Converts a dictionary into a toml table, mirroring `populate_toml_dictionary` in reverse.
+///
+/// # Arguments
+///
+/// `dictionary` - The dictionary to convert into a toml table.
+fn dictionary_to_toml_table(dictionary: &Dictionary) -> Value {
+	let mut table = toml::map::Map::new();
+
+	for key in dictionary.keys().iter() {
+		let key_str = Variant::to_string(&key);
+		let value = dictionary.get(&key);
+		table.insert(key_str, variant_to_toml_value(&value));
+	}
+
+	return Value::Table(table);
+}
+
+/// Converts a single variant into the toml value it represents.
+///
+/// Godot types such as `Vector2` or `Color` are encoded back into the same parenthesized string form that
+/// `encode_godot_types` understands, so that a file written by `save_toml` re-parses identically through
+/// `parse_toml`. Known limitation: a plain `GodotString` is written out verbatim, so one whose text exactly matches
+/// a Godot-type literal (e.g. `"Vector2( 1, 2 )"`) round-trips back as that type instead of as a string, since
+/// `populate_toml_dictionary` can't tell the two apart once they're both just TOML strings. Likewise a homogeneous
+/// array is written as a plain TOML array and re-parses as the matching 32-bit pool array, see `encode_pool_array`.
+///
+/// # Arguments
+///
+/// `value` - The variant to convert.
+fn variant_to_toml_value(value: &Variant) -> Value {
+	match value.get_type() {
+		VariantType::I64 => Value::Integer(value.to_i64()),
+		VariantType::F64 => Value::Float(value.to_f64()),
+		VariantType::Bool => Value::Boolean(value.to_bool()),
+		VariantType::GodotString => Value::String(Variant::to_string(value)),
+		VariantType::Vector2 => Value::String(decode_vector2(&value.to_vector2())),
+		VariantType::Vector3 => Value::String(decode_vector3(&value.to_vector3())),
+		VariantType::Color => Value::String(decode_color(&value.to_color())),
+		VariantType::Rect2 => Value::String(decode_rect2(&value.to_rect2())),
+		VariantType::Plane => Value::String(decode_plane(&value.to_plane())),
+		VariantType::Transform2D => Value::String(decode_transform2d(&value.to_transform2d())),
+		VariantType::Basis => Value::String(decode_basis(&value.to_basis())),
+		VariantType::Transform => Value::String(decode_transform(&value.to_transform())),
+		VariantType::Quat => Value::String(decode_quat(&value.to_quat())),
+		VariantType::Aabb => Value::String(decode_aabb(&value.to_aabb())),
+		VariantType::NodePath => Value::String(decode_nodepath(&value.to_node_path())),
+		VariantType::Dictionary => dictionary_to_toml_table(&value.to_dictionary()),
+		VariantType::VariantArray => {
+			let arr = value.to_array();
+			let mut values = vec![];
+			for item in arr.iter() {
+				values.push(variant_to_toml_value(&item));
+			}
+			Value::Array(values)
+		}
+		VariantType::Int32Array => Value::Array(
+			value
+				.to_int32_array()
+				.read()
+				.iter()
+				.map(|i| Value::Integer(*i as i64))
+				.collect(),
+		),
+		VariantType::Float32Array => Value::Array(
+			value
+				.to_float32_array()
+				.read()
+				.iter()
+				.map(|f| Value::Float(*f as f64))
+				.collect(),
+		),
+		VariantType::StringArray => Value::Array(
+			value
+				.to_string_array()
+				.read()
+				.iter()
+				.map(|s| Value::String(s.to_string()))
+				.collect(),
+		),
+		VariantType::Vector2Array => Value::Array(
+			value
+				.to_vector2_array()
+				.read()
+				.iter()
+				.map(|v| Value::String(decode_vector2(v)))
+				.collect(),
+		),
+		VariantType::Vector3Array => Value::Array(
+			value
+				.to_vector3_array()
+				.read()
+				.iter()
+				.map(|v| Value::String(decode_vector3(v)))
+				.collect(),
+		),
+		VariantType::ColorArray => Value::Array(
+			value
+				.to_color_array()
+				.read()
+				.iter()
+				.map(|c| Value::String(decode_color(c)))
+				.collect(),
+		),
+		_ => Value::String(Variant::to_string(value)),
+	}
+}
+
+/// Returns the `Vector2( x, y )` string form of a Vector2 that `encode_vector2` can parse back.
+///
+/// # Arguments
+///
+/// `vector2` - The Vector2 to encode.
+fn decode_vector2(vector2: &Vector2) -> String {
+	format!("Vector2( {}, {} )", vector2.x, vector2.y)
+}
+
+/// Returns the `Vector3( x, y, z )` string form of a Vector3 that `encode_vector3` can parse back.
+///
+/// # Arguments
+///
+/// `vector3` - The Vector3 to encode.
+fn decode_vector3(vector3: &Vector3) -> String {
+	format!("Vector3( {}, {}, {} )", vector3.x, vector3.y, vector3.z)
+}
+
+/// Returns the `Rect2( Vector2(...), Vector2(...) )` string form of a Rect2 that `encode_rect2` can parse back.
+///
+/// # Arguments
+///
+/// `rect2` - The Rect2 to encode.
+fn decode_rect2(rect2: &Rect2) -> String {
+	let pos_vec = Vector2::new(rect2.origin.x, rect2.origin.y);
+	let size_vec = Vector2::new(rect2.size.width, rect2.size.height);
+
+	format!(
+		"Rect2( {}, {} )",
+		decode_vector2(&pos_vec),
+		decode_vector2(&size_vec)
+	)
+}
+
+/// Returns the `Plane( Vector3(...), d )` string form of a Plane that `encode_plane` can parse back.
+///
+/// # Arguments
+///
+/// `plane` - The Plane to encode.
+fn decode_plane(plane: &Plane) -> String {
+	format!("Plane( {}, {} )", decode_vector3(&plane.normal), plane.d)
+}
+
+/// Returns the `Transform2D( Vector2(...), Vector2(...), Vector2(...) )` string form of a Transform2D that
+/// `encode_transform2d` can parse back.
+///
+/// # Arguments
+///
+/// `transform2d` - The Transform2D to encode.
+fn decode_transform2d(transform2d: &Transform2D) -> String {
+	let x_axis_vec = Vector2::new(transform2d.m11, transform2d.m12);
+	let y_axis_vec = Vector2::new(transform2d.m21, transform2d.m22);
+	let origin_vec = Vector2::new(transform2d.m31, transform2d.m32);
+
+	format!(
+		"Transform2D( {}, {}, {} )",
+		decode_vector2(&x_axis_vec),
+		decode_vector2(&y_axis_vec),
+		decode_vector2(&origin_vec)
+	)
+}
+
+/// Returns the `Basis( Vector3(...), Vector3(...), Vector3(...) )` string form of a Basis that `encode_basis` can
+/// parse back.
+///
+/// # Arguments
+///
+/// `basis` - The Basis to encode.
+fn decode_basis(basis: &Basis) -> String {
+	format!(
+		"Basis( {}, {}, {} )",
+		decode_vector3(&basis.elements[0]),
+		decode_vector3(&basis.elements[1]),
+		decode_vector3(&basis.elements[2])
+	)
+}
+
+/// Returns the `Transform( Vector3(...), Vector3(...), Vector3(...), Vector3(...) )` string form of a Transform that
+/// `encode_transform` can parse back.
+///
+/// # Arguments
+///
+/// `transform` - The Transform to encode.
+fn decode_transform(transform: &Transform) -> String {
+	format!(
+		"Transform( {}, {}, {}, {} )",
+		decode_vector3(&transform.basis.elements[0]),
+		decode_vector3(&transform.basis.elements[1]),
+		decode_vector3(&transform.basis.elements[2]),
+		decode_vector3(&transform.origin)
+	)
+}
+
+/// Returns the `Color( r, g, b, a )` string form of a Color that `encode_color` can parse back.
+///
+/// # Arguments
+///
+/// `color` - The Color to encode.
+fn decode_color(color: &Color) -> String {
+	format!(
+		"Color( {}, {}, {}, {} )",
+		color.r, color.g, color.b, color.a
+	)
+}
+
+/// Returns the `Quat( x, y, z, w )` string form of a Quat that `encode_quat` can parse back.
+///
+/// # Arguments
+///
+/// `quat` - The Quat to encode.
+fn decode_quat(quat: &Quat) -> String {
+	format!("Quat( {}, {}, {}, {} )", quat.x, quat.y, quat.z, quat.w)
+}
+
+/// Returns the `AABB( Vector3(...), Vector3(...) )` string form of an AABB that `encode_aabb` can parse back.
+///
+/// # Arguments
+///
+/// `aabb` - The AABB to encode.
+fn decode_aabb(aabb: &Aabb) -> String {
+	format!(
+		"AABB( {}, {} )",
+		decode_vector3(&aabb.position),
+		decode_vector3(&aabb.size)
+	)
+}
+
+/// Returns the `NodePath( "path/to/node" )` string form of a NodePath that `encode_nodepath` can parse back.
+///
+/// # Arguments
+///
+/// `node_path` - The NodePath to encode.
+fn decode_nodepath(node_path: &NodePath) -> String {
+	format!(
+		"NodePath( \"{}\" )",
+		Variant::to_string(&Variant::from_node_path(node_path))
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Runs a dictionary through the same toml encode/decode path that `save_toml` and `parse_toml_string` use,
+	/// without needing a live Godot `File`.
+	fn round_trip(dictionary: &Dictionary) -> Dictionary {
+		let toml_table = dictionary_to_toml_table(dictionary);
+		let toml_string = toml::to_string(&toml_table).expect("test dictionary should serialize to toml");
+		let value: Value = toml::from_str(&toml_string).expect("test toml should parse back");
+
+		let mut out = Dictionary::new();
+		let mut errors = vec![];
+		match value.as_table() {
+			Some(table) => populate_toml_dictionary(&value, &mut out, table, &mut errors),
+			None => panic!("round-tripped toml did not parse back into a table"),
+		}
+
+		assert!(errors.is_empty(), "round trip produced errors: {:?}", errors);
+
+		out
+	}
+
+	#[test]
+	fn round_trips_primitives() {
+		let mut dictionary = Dictionary::new();
+		dictionary.set(&Variant::from_str("int"), &Variant::from_i64(42));
+		dictionary.set(&Variant::from_str("float"), &Variant::from_f64(1.5));
+		dictionary.set(&Variant::from_str("bool"), &Variant::from_bool(true));
+		dictionary.set(&Variant::from_str("string"), &Variant::from_str("hello"));
+
+		let out = round_trip(&dictionary);
+
+		assert_eq!(out.get(&Variant::from_str("int")).to_i64(), 42);
+		assert_eq!(out.get(&Variant::from_str("float")).to_f64(), 1.5);
+		assert_eq!(out.get(&Variant::from_str("bool")).to_bool(), true);
+		assert_eq!(
+			Variant::to_string(&out.get(&Variant::from_str("string"))),
+			"hello"
+		);
+	}
+
+	#[test]
+	fn round_trips_godot_geometry_types() {
+		let vector2 = Vector2::new(1.0, 2.0);
+		let vector3 = Vector3::new(1.0, 2.0, 3.0);
+		let color = Color::rgba(0.1, 0.2, 0.3, 0.4);
+		let rect2 = encode_rect2(Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0));
+		let plane = encode_plane(Vector3::new(1.0, 2.0, 3.0), "4").unwrap();
+		let transform2d = encode_transform2d(
+			Vector2::new(1.0, 0.0),
+			Vector2::new(0.0, 1.0),
+			Vector2::new(5.0, 6.0),
+		);
+		let basis = encode_basis(
+			Vector3::new(1.0, 0.0, 0.0),
+			Vector3::new(0.0, 1.0, 0.0),
+			Vector3::new(0.0, 0.0, 1.0),
+		);
+		let transform = encode_transform(
+			Vector3::new(1.0, 0.0, 0.0),
+			Vector3::new(0.0, 1.0, 0.0),
+			Vector3::new(0.0, 0.0, 1.0),
+			Vector3::new(7.0, 8.0, 9.0),
+		);
+		let quat = encode_quat("0", "0", "0", "1").unwrap();
+		let aabb = encode_aabb(Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0));
+
+		let mut dictionary = Dictionary::new();
+		dictionary.set(&Variant::from_str("vector2"), &Variant::from_vector2(&vector2));
+		dictionary.set(&Variant::from_str("vector3"), &Variant::from_vector3(&vector3));
+		dictionary.set(&Variant::from_str("color"), &Variant::from_color(&color));
+		dictionary.set(
+			&Variant::from_str("node_path"),
+			&Variant::from_node_path(&NodePath::from_str("path/to/node")),
+		);
+		dictionary.set(&Variant::from_str("rect2"), &Variant::from_rect2(&rect2));
+		dictionary.set(&Variant::from_str("plane"), &Variant::from_plane(&plane));
+		dictionary.set(
+			&Variant::from_str("transform2d"),
+			&Variant::from_transform2d(&transform2d),
+		);
+		dictionary.set(&Variant::from_str("basis"), &Variant::from_basis(&basis));
+		dictionary.set(
+			&Variant::from_str("transform"),
+			&Variant::from_transform(&transform),
+		);
+		dictionary.set(&Variant::from_str("quat"), &Variant::from_quat(&quat));
+		dictionary.set(&Variant::from_str("aabb"), &Variant::from_aabb(&aabb));
+
+		let out = round_trip(&dictionary);
+
+		assert_eq!(out.get(&Variant::from_str("vector2")).to_vector2(), vector2);
+		assert_eq!(out.get(&Variant::from_str("vector3")).to_vector3(), vector3);
+		assert_eq!(out.get(&Variant::from_str("color")).to_color(), color);
+		assert_eq!(
+			Variant::to_string(&Variant::from_node_path(
+				&out.get(&Variant::from_str("node_path")).to_node_path()
+			)),
+			"path/to/node"
+		);
+		assert_eq!(
+			decode_rect2(&out.get(&Variant::from_str("rect2")).to_rect2()),
+			decode_rect2(&rect2)
+		);
+		assert_eq!(
+			decode_plane(&out.get(&Variant::from_str("plane")).to_plane()),
+			decode_plane(&plane)
+		);
+		assert_eq!(
+			decode_transform2d(&out.get(&Variant::from_str("transform2d")).to_transform2d()),
+			decode_transform2d(&transform2d)
+		);
+		assert_eq!(
+			decode_basis(&out.get(&Variant::from_str("basis")).to_basis()),
+			decode_basis(&basis)
+		);
+		assert_eq!(
+			decode_transform(&out.get(&Variant::from_str("transform")).to_transform()),
+			decode_transform(&transform)
+		);
+		assert_eq!(
+			decode_quat(&out.get(&Variant::from_str("quat")).to_quat()),
+			decode_quat(&quat)
+		);
+		assert_eq!(
+			decode_aabb(&out.get(&Variant::from_str("aabb")).to_aabb()),
+			decode_aabb(&aabb)
+		);
+	}
+
+	#[test]
+	fn round_trips_pool_arrays() {
+		let mut int_pool = Int32Array::new();
+		int_pool.push(1);
+		int_pool.push(2);
+		int_pool.push(3);
+
+		let mut float_pool = Float32Array::new();
+		float_pool.push(1.5);
+		float_pool.push(2.5);
+
+		let mut string_pool = StringArray::new();
+		string_pool.push(&GodotString::from_str("one"));
+		string_pool.push(&GodotString::from_str("two"));
+
+		let mut vec2_pool = Vector2Array::new();
+		vec2_pool.push(&Vector2::new(1.0, 2.0));
+		vec2_pool.push(&Vector2::new(3.0, 4.0));
+
+		let mut vec3_pool = Vector3Array::new();
+		vec3_pool.push(&Vector3::new(1.0, 2.0, 3.0));
+		vec3_pool.push(&Vector3::new(4.0, 5.0, 6.0));
+
+		let mut color_pool = ColorArray::new();
+		color_pool.push(&Color::rgba(0.1, 0.2, 0.3, 0.4));
+		color_pool.push(&Color::rgba(0.5, 0.6, 0.7, 0.8));
+
+		let mut dictionary = Dictionary::new();
+		dictionary.set(
+			&Variant::from_str("ints"),
+			&Variant::from_int32_array(&int_pool),
+		);
+		dictionary.set(
+			&Variant::from_str("floats"),
+			&Variant::from_float32_array(&float_pool),
+		);
+		dictionary.set(
+			&Variant::from_str("strings"),
+			&Variant::from_string_array(&string_pool),
+		);
+		dictionary.set(
+			&Variant::from_str("vec2s"),
+			&Variant::from_vector2_array(&vec2_pool),
+		);
+		dictionary.set(
+			&Variant::from_str("vec3s"),
+			&Variant::from_vector3_array(&vec3_pool),
+		);
+		dictionary.set(
+			&Variant::from_str("colors"),
+			&Variant::from_color_array(&color_pool),
+		);
+
+		let out = round_trip(&dictionary);
+
+		let out_ints = out.get(&Variant::from_str("ints")).to_int32_array();
+		assert_eq!(out_ints.read().to_vec(), vec![1, 2, 3]);
+
+		let out_floats = out.get(&Variant::from_str("floats")).to_float32_array();
+		assert_eq!(out_floats.read().to_vec(), vec![1.5, 2.5]);
+
+		let out_strings = out.get(&Variant::from_str("strings")).to_string_array();
+		assert_eq!(
+			out_strings
+				.read()
+				.iter()
+				.map(|s| s.to_string())
+				.collect::<Vec<String>>(),
+			vec!["one".to_string(), "two".to_string()]
+		);
+
+		let out_vec2s = out.get(&Variant::from_str("vec2s")).to_vector2_array();
+		assert_eq!(
+			out_vec2s.read().to_vec(),
+			vec![Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0)]
+		);
+
+		let out_vec3s = out.get(&Variant::from_str("vec3s")).to_vector3_array();
+		assert_eq!(
+			out_vec3s.read().to_vec(),
+			vec![Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0)]
+		);
+
+		let out_colors = out.get(&Variant::from_str("colors")).to_color_array();
+		assert_eq!(
+			out_colors.read().to_vec(),
+			vec![
+				Color::rgba(0.1, 0.2, 0.3, 0.4),
+				Color::rgba(0.5, 0.6, 0.7, 0.8)
+			]
+		);
+	}
+
+	#[test]
+	fn round_trips_nested_dictionaries_and_arrays() {
+		let mut inner = Dictionary::new();
+		inner.set(&Variant::from_str("nested_int"), &Variant::from_i64(7));
+
+		let mut arr = VariantArray::new();
+		arr.push(&Variant::from_i64(1));
+		arr.push(&Variant::from_str("two"));
+
+		let mut dictionary = Dictionary::new();
+		dictionary.set(&Variant::from_str("inner"), &Variant::from_dictionary(&inner));
+		dictionary.set(&Variant::from_str("mixed_array"), &Variant::from_array(&arr));
+
+		let out = round_trip(&dictionary);
+
+		let out_inner = out.get(&Variant::from_str("inner")).to_dictionary();
+		assert_eq!(
+			out_inner.get(&Variant::from_str("nested_int")).to_i64(),
+			7
+		);
+
+		let out_arr = out.get(&Variant::from_str("mixed_array")).to_array();
+		assert_eq!(out_arr.get(0).to_i64(), 1);
+		assert_eq!(Variant::to_string(&out_arr.get(1)), "two");
+	}
 }